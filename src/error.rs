@@ -0,0 +1,103 @@
+use rmcp::ErrorData as McpError;
+use std::fmt;
+
+/// A failure while talking to the Webpublication API.
+///
+/// Converted into an `McpError` at the tool boundary, with the error kind and
+/// any HTTP details attached as structured data.
+#[derive(Debug)]
+pub enum WebPubError {
+    /// The request could not be sent, or the backend answered with an error status.
+    Http {
+        message: String,
+        status: Option<u16>,
+        endpoint: Option<String>,
+    },
+    /// The backend answered with a body that is not the expected JSON.
+    Parse(String),
+    /// Credentials are missing or were rejected by the backend.
+    Auth {
+        message: String,
+        status: Option<u16>,
+    },
+    /// The backend has no such resource (404), usually because of a wrong gid.
+    NotFound { message: String, endpoint: String },
+    /// The tool was called with invalid arguments.
+    InvalidParams(String),
+}
+
+impl WebPubError {
+    /// An `Http` error for a request that never got an answer.
+    pub fn request(message: impl Into<String>) -> Self {
+        WebPubError::Http {
+            message: message.into(),
+            status: None,
+            endpoint: None,
+        }
+    }
+
+    fn kind(&self) -> &'static str {
+        match self {
+            WebPubError::Http { .. } => "http",
+            WebPubError::Parse(_) => "parse",
+            WebPubError::Auth { .. } => "auth",
+            WebPubError::NotFound { .. } => "not_found",
+            WebPubError::InvalidParams(_) => "invalid_params",
+        }
+    }
+
+    fn data(&self) -> serde_json::Value {
+        let mut data = serde_json::json!({ "kind": self.kind() });
+        let (status, endpoint) = match self {
+            WebPubError::Http {
+                status, endpoint, ..
+            } => (*status, endpoint.as_deref()),
+            WebPubError::Auth { status, .. } => (*status, None),
+            WebPubError::NotFound { endpoint, .. } => (Some(404), Some(endpoint.as_str())),
+            WebPubError::Parse(_) | WebPubError::InvalidParams(_) => (None, None),
+        };
+        if let Some(status) = status {
+            data["status"] = status.into();
+        }
+        if let Some(endpoint) = endpoint {
+            data["endpoint"] = endpoint.into();
+        }
+        data
+    }
+}
+
+impl fmt::Display for WebPubError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            WebPubError::Http { message, .. } | WebPubError::Auth { message, .. } => {
+                f.write_str(message)
+            }
+            WebPubError::NotFound { message, .. } => write!(
+                f,
+                "{}. The requested resource does not exist: check the ids passed to the tool, \
+                 and call get_recent_resources to look up the right globalId",
+                message
+            ),
+            WebPubError::Parse(message) => write!(f, "Failed to parse response: {}", message),
+            WebPubError::InvalidParams(message) => f.write_str(message),
+        }
+    }
+}
+
+impl std::error::Error for WebPubError {}
+
+impl From<WebPubError> for McpError {
+    fn from(error: WebPubError) -> Self {
+        let message = error.to_string();
+        let data = Some(error.data());
+        match error {
+            // A 404 almost always means a wrong gid, which the agent can fix
+            WebPubError::InvalidParams(_) | WebPubError::NotFound { .. } => {
+                McpError::invalid_params(message, data)
+            }
+            WebPubError::Http { .. } | WebPubError::Parse(_) | WebPubError::Auth { .. } => {
+                McpError::internal_error(message, data)
+            }
+        }
+    }
+}
@@ -1,28 +1,170 @@
+mod error;
 mod models;
+mod prompts;
 mod service;
 
 use anyhow::Result;
-use rmcp::ServiceExt;
+use rmcp::{transport::sse_server::SseServer, ServiceExt};
+use std::net::SocketAddr;
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 
 use service::WebPublication;
 
-#[tokio::main]
-async fn main() -> Result<()> {
-    tracing_subscriber::registry()
-        .with(
-            tracing_subscriber::EnvFilter::try_from_default_env()
-                .unwrap_or_else(|_| "mcp_webpublication_server=info".into()),
-        )
-        .with(tracing_subscriber::fmt::layer().with_writer(std::io::stderr))
-        .init();
+const DEFAULT_SSE_HOST: &str = "127.0.0.1";
+const DEFAULT_SSE_PORT: u16 = 8000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Transport {
+    Stdio,
+    Sse,
+}
+
+impl Transport {
+    /// Reads the transport from `--transport <stdio|sse>`, falling back to `MCP_TRANSPORT`.
+    fn from_args_or_env() -> Result<Self> {
+        let mut args = std::env::args().skip(1);
+        let mut value = None;
+        while let Some(arg) = args.next() {
+            if arg == "--transport" {
+                value = args.next();
+            } else if let Some(transport) = arg.strip_prefix("--transport=") {
+                value = Some(transport.to_string());
+            }
+        }
+
+        match value.or_else(|| std::env::var("MCP_TRANSPORT").ok()) {
+            None => Ok(Transport::Stdio),
+            Some(value) => match value.to_ascii_lowercase().as_str() {
+                "stdio" => Ok(Transport::Stdio),
+                "sse" => Ok(Transport::Sse),
+                other => Err(anyhow::anyhow!(
+                    "Unknown transport '{}', expected stdio or sse",
+                    other
+                )),
+            },
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum LogFormat {
+    Pretty,
+    Json,
+}
+
+impl LogFormat {
+    /// Reads the log format from `LOG_FORMAT`, defaulting to pretty.
+    fn from_env() -> Result<Self> {
+        match std::env::var("LOG_FORMAT") {
+            Err(_) => Ok(LogFormat::Pretty),
+            Ok(value) => match value.to_ascii_lowercase().as_str() {
+                "pretty" => Ok(LogFormat::Pretty),
+                "json" => Ok(LogFormat::Json),
+                other => Err(anyhow::anyhow!(
+                    "Unknown log format '{}', expected pretty or json",
+                    other
+                )),
+            },
+        }
+    }
+}
+
+fn init_tracing(format: LogFormat) {
+    let filter = tracing_subscriber::EnvFilter::try_from_default_env()
+        .unwrap_or_else(|_| "mcp_webpublication_server=info".into());
+    let layer = tracing_subscriber::fmt::layer().with_writer(std::io::stderr);
+
+    match format {
+        LogFormat::Pretty => tracing_subscriber::registry()
+            .with(filter)
+            .with(layer)
+            .init(),
+        LogFormat::Json => tracing_subscriber::registry()
+            .with(filter)
+            .with(layer.json())
+            .init(),
+    }
+}
+
+fn sse_bind_address() -> Result<SocketAddr> {
+    let host = std::env::var("MCP_HOST").unwrap_or_else(|_| DEFAULT_SSE_HOST.to_string());
+    let port = match std::env::var("MCP_PORT") {
+        Ok(port) => port
+            .parse::<u16>()
+            .map_err(|_| anyhow::anyhow!("MCP_PORT has an invalid value: {}", port))?,
+        Err(_) => DEFAULT_SSE_PORT,
+    };
+
+    format!("{}:{}", host, port)
+        .parse()
+        .map_err(|e| anyhow::anyhow!("Invalid SSE bind address {}:{}: {}", host, port, e))
+}
+
+/// Resolves on Ctrl-C (SIGINT) or, on Unix, SIGTERM.
+async fn shutdown_signal() -> Result<()> {
+    #[cfg(unix)]
+    {
+        let mut sigterm =
+            tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate())?;
+        tokio::select! {
+            result = tokio::signal::ctrl_c() => result?,
+            _ = sigterm.recv() => {}
+        }
+    }
+    #[cfg(not(unix))]
+    tokio::signal::ctrl_c().await?;
+
+    tracing::info!("Shutdown signal received, starting graceful shutdown");
+    Ok(())
+}
+
+async fn run() -> Result<()> {
+    // Loaded here too so LOG_FORMAT and RUST_LOG can come from .env
+    dotenv::dotenv().ok();
+    init_tracing(LogFormat::from_env()?);
 
     tracing::info!("Starting MCP Webpublication server");
 
     let webpub = WebPublication::new()?;
-    let server = webpub.serve(rmcp::transport::stdio()).await?;
-    server.waiting().await?;
 
+    match Transport::from_args_or_env()? {
+        Transport::Stdio => {
+            // The signal is also honoured before the client has initialized
+            let serve = async {
+                let server = webpub.clone().serve(rmcp::transport::stdio()).await?;
+                server.waiting().await?;
+                anyhow::Ok(())
+            };
+
+            tokio::select! {
+                result = serve => result?,
+                result = shutdown_signal() => result?,
+            }
+        }
+        Transport::Sse => {
+            let bind = sse_bind_address()?;
+            tracing::info!("Serving over SSE on http://{}/sse", bind);
+
+            let service = webpub.clone();
+            let ct = SseServer::serve(bind)
+                .await?
+                .with_service(move || service.clone());
+
+            shutdown_signal().await?;
+            ct.cancel();
+        }
+    }
+
+    webpub.shutdown().await;
     tracing::info!("Server shutdown complete");
     Ok(())
 }
+
+fn main() -> Result<()> {
+    let runtime = tokio::runtime::Runtime::new()?;
+    let result = runtime.block_on(run());
+    // The stdio transport reads stdin on a blocking thread that only returns
+    // at EOF, so don't wait for it once the server has shut down
+    runtime.shutdown_background();
+    result
+}
@@ -1,15 +1,452 @@
-use schemars::JsonSchema;
-use serde::{Deserialize, Serialize};
+use schemars::{json_schema, JsonSchema, Schema, SchemaGenerator};
+use serde::{de, Deserialize, Deserializer, Serialize};
+use std::str::FromStr;
 
 #[derive(Debug, Deserialize, Serialize, JsonSchema)]
 pub struct GetResourceRequest {
+    #[serde(deserialize_with = "deserialize_gid")]
+    #[schemars(schema_with = "gid_schema")]
     pub resource_gid: i64,
+    pub fields: Option<Vec<String>>,
+    pub client_id: Option<String>,
+    pub locale: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema)]
+pub struct GetResourceVersionsRequest {
+    #[serde(deserialize_with = "deserialize_gid")]
+    #[schemars(schema_with = "gid_schema")]
+    pub resource_gid: i64,
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema)]
+pub struct GetResourceVersionRequest {
+    #[serde(deserialize_with = "deserialize_gid")]
+    #[schemars(schema_with = "gid_schema")]
+    pub resource_gid: i64,
+    pub version_id: String,
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema)]
+pub struct RestoreResourceVersionRequest {
+    #[serde(deserialize_with = "deserialize_gid")]
+    #[schemars(schema_with = "gid_schema")]
+    pub resource_gid: i64,
+    pub version_id: String,
+    pub idempotency_key: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema)]
+pub struct GetPublicationSettingsRequest {
+    #[serde(deserialize_with = "deserialize_gid")]
+    #[schemars(schema_with = "gid_schema")]
+    pub resource_gid: i64,
+    pub locale: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema)]
+pub struct RecentResourcesRequest {
+    pub items_per_page: Option<u32>,
+    pub page_num: Option<u32>,
+    pub include: Option<String>,
+    pub force_refresh: Option<bool>,
+    pub sort_by: Option<String>,
+    pub order: Option<String>,
+    pub client_id: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ResourceKind {
+    Publication,
+    Folder,
+    Template,
+}
+
+impl ResourceKind {
+    pub fn as_str(&self) -> &str {
+        match self {
+            ResourceKind::Publication => "PUBLICATION",
+            ResourceKind::Folder => "FOLDER",
+            ResourceKind::Template => "TEMPLATE",
+        }
+    }
+
+    /// Parses a comma-joined list such as `PUBLICATION,FOLDER`.
+    pub fn parse_list(value: &str) -> Result<Vec<Self>, String> {
+        let kinds = value
+            .split(',')
+            .map(str::trim)
+            .filter(|kind| !kind.is_empty())
+            .map(str::parse)
+            .collect::<Result<Vec<Self>, String>>()?;
+
+        if kinds.is_empty() {
+            return Err("include must contain at least one resource kind".to_string());
+        }
+
+        Ok(kinds)
+    }
+}
+
+impl FromStr for ResourceKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_uppercase().as_str() {
+            "PUBLICATION" => Ok(ResourceKind::Publication),
+            "FOLDER" => Ok(ResourceKind::Folder),
+            "TEMPLATE" => Ok(ResourceKind::Template),
+            other => Err(format!(
+                "Unknown resource kind '{}', expected PUBLICATION, FOLDER or TEMPLATE",
+                other
+            )),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SortBy {
+    Modified,
+    Label,
+}
+
+impl FromStr for SortBy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "modified" => Ok(SortBy::Modified),
+            "label" => Ok(SortBy::Label),
+            other => Err(format!(
+                "Unknown sort_by '{}', expected modified or label",
+                other
+            )),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SortOrder {
+    Asc,
+    Desc,
+}
+
+impl FromStr for SortOrder {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "asc" => Ok(SortOrder::Asc),
+            "desc" => Ok(SortOrder::Desc),
+            other => Err(format!("Unknown order '{}', expected asc or desc", other)),
+        }
+    }
+}
+
+/// Which date `find_publications_by_date` filters on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DateField {
+    Modified,
+    Created,
+}
+
+impl FromStr for DateField {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "modified" => Ok(DateField::Modified),
+            "created" => Ok(DateField::Created),
+            other => Err(format!(
+                "Unknown date_field '{}', expected modified or created",
+                other
+            )),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema)]
+pub struct FindPublicationsByDateRequest {
+    pub from: Option<String>,
+    pub to: Option<String>,
+    pub date_field: Option<String>,
 }
 
 #[derive(Debug, Deserialize, Serialize, JsonSchema)]
 pub struct ToggleWishlistRequest {
+    #[serde(deserialize_with = "deserialize_gid")]
+    #[schemars(schema_with = "gid_schema")]
     pub publication_gid: i64,
     pub wishlist_enabled: bool,
+    pub idempotency_key: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema)]
+pub struct ToggleWishlistBulkRequest {
+    #[serde(deserialize_with = "deserialize_gids")]
+    #[schemars(schema_with = "gids_schema")]
+    pub publication_gids: Vec<i64>,
+    pub wishlist_enabled: bool,
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema)]
+pub struct DeletePublicationRequest {
+    #[serde(deserialize_with = "deserialize_gid")]
+    #[schemars(schema_with = "gid_schema")]
+    pub publication_gid: i64,
+    pub idempotency_key: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema)]
+pub struct GetGalleriesRequest {
+    #[serde(default, deserialize_with = "deserialize_optional_gid")]
+    #[schemars(schema_with = "optional_gid_schema")]
+    pub publication_gid: Option<i64>,
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema)]
+pub struct ValidatePublicationRequest {
+    #[serde(deserialize_with = "deserialize_gid")]
+    #[schemars(schema_with = "gid_schema")]
+    pub publication_gid: i64,
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema)]
+pub struct GetPagesRequest {
+    #[serde(deserialize_with = "deserialize_gid")]
+    #[schemars(schema_with = "gid_schema")]
+    pub publication_gid: i64,
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema)]
+pub struct GetPageRequest {
+    #[serde(deserialize_with = "deserialize_gid")]
+    #[schemars(schema_with = "gid_schema")]
+    pub page_gid: i64,
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema)]
+pub struct GetPublicationStructureRequest {
+    #[serde(deserialize_with = "deserialize_gid")]
+    #[schemars(schema_with = "gid_schema")]
+    pub publication_gid: i64,
+    /// Number of levels to return; deeper nodes are replaced by a `childCount`.
+    pub depth: Option<u32>,
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema)]
+pub struct UpdatePageContentRequest {
+    #[serde(deserialize_with = "deserialize_gid")]
+    #[schemars(schema_with = "gid_schema")]
+    pub page_gid: i64,
+    /// The new page content: an HTML string or structured blocks.
+    pub content: serde_json::Value,
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema)]
+pub struct RenderPagePreviewRequest {
+    #[serde(deserialize_with = "deserialize_gid")]
+    #[schemars(schema_with = "gid_schema")]
+    pub page_gid: i64,
+    pub format: Option<String>,
+    pub width: Option<u32>,
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema)]
+pub struct GetMembershipsRequest {
+    #[serde(deserialize_with = "deserialize_gid")]
+    #[schemars(schema_with = "gid_schema")]
+    pub publication_gid: i64,
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema)]
+pub struct GetMembershipStatusRequest {
+    #[serde(deserialize_with = "deserialize_gid")]
+    #[schemars(schema_with = "gid_schema")]
+    pub publication_gid: i64,
+    pub email: Option<String>,
+    pub member_id: Option<i64>,
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema)]
+pub struct GetCustomizationRequest {
+    #[serde(deserialize_with = "deserialize_gid")]
+    #[schemars(schema_with = "gid_schema")]
+    pub publication_gid: i64,
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema)]
+pub struct UpdateCustomizationRequest {
+    #[serde(deserialize_with = "deserialize_gid")]
+    #[schemars(schema_with = "gid_schema")]
+    pub publication_gid: i64,
+    pub changes: serde_json::Map<String, serde_json::Value>,
+    pub idempotency_key: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema)]
+pub struct StartEnrichmentRequest {
+    #[serde(deserialize_with = "deserialize_gid")]
+    #[schemars(schema_with = "gid_schema")]
+    pub publication_gid: i64,
+    pub enrichment_type: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema)]
+pub struct GetEnrichmentStatusRequest {
+    pub job_id: String,
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema)]
+pub struct GetEnrichmentTagsRequest {
+    #[serde(deserialize_with = "deserialize_gid")]
+    #[schemars(schema_with = "gid_schema")]
+    pub publication_gid: i64,
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema)]
+pub struct UpdateEnrichmentTagsRequest {
+    #[serde(deserialize_with = "deserialize_gid")]
+    #[schemars(schema_with = "gid_schema")]
+    pub publication_gid: i64,
+    pub tags: Vec<String>,
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema)]
+pub struct FindPublicationRequest {
+    pub name: String,
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema)]
+pub struct UpdatePublicationTitleRequest {
+    #[serde(deserialize_with = "deserialize_gid")]
+    #[schemars(schema_with = "gid_schema")]
+    pub publication_gid: i64,
+    pub title: String,
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema)]
+pub struct SetCoverImageRequest {
+    #[serde(deserialize_with = "deserialize_gid")]
+    #[schemars(schema_with = "gid_schema")]
+    pub publication_gid: i64,
+    pub rel_url: String,
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema)]
+pub struct UploadImageRequest {
+    pub data: String,
+    pub filename: String,
+    pub mime_type: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema)]
+pub struct GetGalleryImagesRequest {
+    #[serde(deserialize_with = "deserialize_gid")]
+    #[schemars(schema_with = "gid_schema")]
+    pub gallery_gid: i64,
+    pub items_per_page: Option<u32>,
+    pub page_num: Option<u32>,
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema)]
+pub struct GetPublicationStatusRequest {
+    #[serde(deserialize_with = "deserialize_gid")]
+    #[schemars(schema_with = "gid_schema")]
+    pub publication_gid: i64,
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema)]
+pub struct PublishPublicationRequest {
+    #[serde(deserialize_with = "deserialize_gid")]
+    #[schemars(schema_with = "gid_schema")]
+    pub publication_gid: i64,
+    pub schedule_at: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema)]
+pub struct UnpublishPublicationRequest {
+    #[serde(deserialize_with = "deserialize_gid")]
+    #[schemars(schema_with = "gid_schema")]
+    pub publication_gid: i64,
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema)]
+pub struct SetPublicationStatusRequest {
+    #[serde(deserialize_with = "deserialize_gid")]
+    #[schemars(schema_with = "gid_schema")]
+    pub publication_gid: i64,
+    pub published: bool,
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema)]
+pub struct MoveResourceRequest {
+    #[serde(deserialize_with = "deserialize_gid")]
+    #[schemars(schema_with = "gid_schema")]
+    pub resource_gid: i64,
+    #[serde(deserialize_with = "deserialize_gid")]
+    #[schemars(schema_with = "gid_schema")]
+    pub target_folder_gid: i64,
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema)]
+pub struct DuplicatePublicationRequest {
+    #[serde(deserialize_with = "deserialize_gid")]
+    #[schemars(schema_with = "gid_schema")]
+    pub publication_gid: i64,
+    pub new_title: Option<String>,
+    pub idempotency_key: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema)]
+pub struct GetDriveAccessTokenRequest {
+    pub rel_url: Option<String>,
+    #[serde(default, deserialize_with = "deserialize_optional_gid")]
+    #[schemars(schema_with = "optional_gid_schema")]
+    pub publication_gid: Option<i64>,
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema)]
+pub struct DownloadDriveFileRequest {
+    pub rel_url: String,
+    pub filename: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema)]
+pub struct ListAllPublicationsRequest {
+    pub limit: Option<u32>,
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema)]
+pub struct CreateGalleryRequest {
+    #[serde(deserialize_with = "deserialize_gid")]
+    #[schemars(schema_with = "gid_schema")]
+    pub publication_gid: i64,
+    pub name: String,
+    pub idempotency_key: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema)]
+pub struct AddImageToGalleryRequest {
+    #[serde(deserialize_with = "deserialize_gid")]
+    #[schemars(schema_with = "gid_schema")]
+    pub gallery_gid: i64,
+    pub rel_url: Option<String>,
+    #[serde(default, deserialize_with = "deserialize_optional_gid")]
+    #[schemars(schema_with = "optional_gid_schema")]
+    pub image_gid: Option<i64>,
+    pub caption: Option<String>,
+    pub idempotency_key: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema)]
+pub struct ReorderGalleryImagesRequest {
+    #[serde(deserialize_with = "deserialize_gid")]
+    #[schemars(schema_with = "gid_schema")]
+    pub gallery_gid: i64,
+    #[serde(deserialize_with = "deserialize_gids")]
+    #[schemars(schema_with = "gids_schema")]
+    pub image_gids: Vec<i64>,
 }
 
 #[derive(Debug, Deserialize, Serialize)]
@@ -18,7 +455,306 @@ pub struct ApiResponse {
     pub data: serde_json::Value,
 }
 
+/// A typed backend response that tools rely on some fields of.
+pub trait ExpectedFields {
+    /// The expected fields, by their JSON name, that the response lacks.
+    fn missing_fields(&self) -> Vec<&'static str>;
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PublicationSettings {
+    pub global_id: Option<i64>,
+    pub label: Option<String>,
+    pub wishlist_enabled: Option<bool>,
+    pub published: Option<bool>,
+    pub cover_image: Option<CoverImage>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+/// A resource listed by `getRecentResources`. Deserialized by hand: the
+/// backend may send several names for the same field in one item, which
+/// serde aliases reject as duplicates, so the first non-null one is used.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RecentResource {
+    pub global_id: Option<i64>,
+    pub label: Option<String>,
+    pub kind: Option<String>,
+    pub modified_at: Option<serde_json::Value>,
+    pub created_at: Option<serde_json::Value>,
+    pub cover_image: Option<CoverImage>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+impl<'de> Deserialize<'de> for RecentResource {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let mut fields = serde_json::Map::deserialize(deserializer)?;
+        // Removes every name of the field, so none of them end up in `extra`
+        let mut take = |names: &[&str]| {
+            names
+                .iter()
+                .filter_map(|name| fields.remove(*name))
+                .reduce(|first, value| if first.is_null() { value } else { first })
+                .filter(|value| !value.is_null())
+        };
+        fn field<T: de::DeserializeOwned, E: de::Error>(
+            name: &str,
+            value: Option<serde_json::Value>,
+        ) -> Result<Option<T>, E> {
+            value
+                .map(serde_json::from_value)
+                .transpose()
+                .map_err(|e| E::custom(format!("{}: {}", name, e)))
+        }
+
+        let global_id = field::<Gid, D::Error>("globalId", take(&["globalId"]))?
+            .map(Gid::into_i64)
+            .transpose()?;
+        let label = field("label", take(&["label"]))?;
+        let kind = field("kind", take(&["kind", "type", "resourceType"]))?;
+        let modified_at = take(&[
+            "modifiedAt",
+            "modificationDate",
+            "lastModified",
+            "updatedAt",
+        ]);
+        let created_at = take(&["createdAt", "creationDate", "createdDate"]);
+        let cover_image = field("coverImage", take(&["coverImage"]))?;
+
+        Ok(Self {
+            global_id,
+            label,
+            kind,
+            modified_at,
+            created_at,
+            cover_image,
+            extra: fields,
+        })
+    }
+}
+
+impl ExpectedFields for PublicationSettings {
+    fn missing_fields(&self) -> Vec<&'static str> {
+        let mut missing = Vec::new();
+        if self.global_id.is_none() {
+            missing.push("globalId");
+        }
+        if self.label.is_none() {
+            missing.push("label");
+        }
+        missing
+    }
+}
+
+impl ExpectedFields for RecentResource {
+    fn missing_fields(&self) -> Vec<&'static str> {
+        let mut missing = Vec::new();
+        if self.global_id.is_none() {
+            missing.push("globalId");
+        }
+        if self.label.is_none() {
+            missing.push("label");
+        }
+        missing
+    }
+}
+
+/// Backend dates come as epoch numbers, ISO strings or `{year, month, ...}`
+/// objects; each form sorts chronologically among values of the same form.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum DateKey {
+    Number(i64),
+    Text(String),
+    Parts(Vec<i64>),
+}
+
+impl RecentResource {
+    pub fn modified_key(&self) -> Option<DateKey> {
+        match self.modified_at.as_ref()? {
+            serde_json::Value::Number(number) => number.as_i64().map(DateKey::Number),
+            serde_json::Value::String(text) => Some(DateKey::Text(text.clone())),
+            serde_json::Value::Object(parts) => Some(DateKey::Parts(
+                [
+                    "year",
+                    "month",
+                    "dayOfMonth",
+                    "day",
+                    "hourOfDay",
+                    "hour",
+                    "minute",
+                    "second",
+                ]
+                .iter()
+                .filter_map(|key| parts.get(*key)?.as_i64())
+                .collect(),
+            )),
+            _ => None,
+        }
+    }
+}
+
+/// Whether and where a publication can be viewed, from its settings.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PublicationLink {
+    pub published: Option<bool>,
+    pub public_url: Option<String>,
+}
+
+/// A paged list response. `total_count` is only sent by some list methods.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ListPage {
+    pub items: Vec<serde_json::Value>,
+    pub total_count: Option<u64>,
+    pub has_more: Option<bool>,
+}
+
+/// The `getResourceVersions` response.
+#[derive(Debug, Deserialize)]
+pub struct ResourceVersions {
+    pub versions: Vec<ResourceVersion>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ResourceVersion {
+    /// Sent as a string or a number.
+    #[serde(deserialize_with = "deserialize_version_id")]
+    pub version_id: String,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CoverImage {
+    pub rel_url: Option<String>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Licence {
+    pub label: Option<String>,
+    pub seats: Option<u32>,
+    pub expiration_date: Option<serde_json::Value>,
+    pub features: Option<Vec<String>>,
+    /// In bytes.
+    pub storage_used: Option<u64>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+impl ExpectedFields for Licence {
+    fn missing_fields(&self) -> Vec<&'static str> {
+        if self.label.is_none() {
+            vec!["label"]
+        } else {
+            Vec::new()
+        }
+    }
+}
+
 #[derive(Debug, Deserialize, Serialize, JsonSchema)]
 pub struct GetImageRequest {
     pub rel_url: String,
 }
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema)]
+pub struct GetCoverImageRequest {
+    pub rel_url: String,
+    pub force_refresh: Option<bool>,
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema)]
+pub struct GetCoverImageByGidRequest {
+    #[serde(deserialize_with = "deserialize_gid")]
+    #[schemars(schema_with = "gid_schema")]
+    pub publication_gid: i64,
+    pub force_refresh: Option<bool>,
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema)]
+pub struct GetImagesRequest {
+    pub rel_urls: Vec<String>,
+}
+
+/// Some clients send large ids as strings, so gids accept both `2473843` and `"2473843"`.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum Gid {
+    Number(i64),
+    String(String),
+}
+
+impl Gid {
+    fn into_i64<E: de::Error>(self) -> Result<i64, E> {
+        match self {
+            Gid::Number(gid) => Ok(gid),
+            Gid::String(gid) => gid
+                .trim()
+                .parse()
+                .map_err(|_| E::custom(format!("invalid gid: {}", gid))),
+        }
+    }
+}
+
+/// The input schema of a gid: `deserialize_gid` takes a number or a numeric string.
+fn gid_schema(_: &mut SchemaGenerator) -> Schema {
+    json_schema!({
+        "type": ["integer", "string"],
+        "pattern": "^\\s*[0-9]+\\s*$"
+    })
+}
+
+fn optional_gid_schema(_: &mut SchemaGenerator) -> Schema {
+    json_schema!({
+        "type": ["integer", "string", "null"],
+        "pattern": "^\\s*[0-9]+\\s*$"
+    })
+}
+
+fn gids_schema(generator: &mut SchemaGenerator) -> Schema {
+    json_schema!({
+        "type": "array",
+        "items": gid_schema(generator)
+    })
+}
+
+/// A gid in a backend response, sent as a number or a numeric string.
+pub fn parse_gid(value: &serde_json::Value) -> Option<i64> {
+    Gid::deserialize(value)
+        .ok()?
+        .into_i64::<serde_json::Error>()
+        .ok()
+}
+
+fn deserialize_gid<'de, D: Deserializer<'de>>(deserializer: D) -> Result<i64, D::Error> {
+    Gid::deserialize(deserializer)?.into_i64()
+}
+
+fn deserialize_optional_gid<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Option<i64>, D::Error> {
+    Option::<Gid>::deserialize(deserializer)?
+        .map(Gid::into_i64)
+        .transpose()
+}
+
+fn deserialize_version_id<'de, D: Deserializer<'de>>(deserializer: D) -> Result<String, D::Error> {
+    match serde_json::Value::deserialize(deserializer)? {
+        serde_json::Value::String(id) => Ok(id),
+        serde_json::Value::Number(id) => Ok(id.to_string()),
+        other => Err(de::Error::custom(format!("invalid version id: {}", other))),
+    }
+}
+
+fn deserialize_gids<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<i64>, D::Error> {
+    Vec::<Gid>::deserialize(deserializer)?
+        .into_iter()
+        .map(Gid::into_i64)
+        .collect()
+}
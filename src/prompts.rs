@@ -0,0 +1,83 @@
+use rmcp::{
+    model::{
+        GetPromptResult, JsonObject, Prompt, PromptArgument, PromptMessage, PromptMessageRole,
+    },
+    ErrorData as McpError,
+};
+
+const SUMMARIZE_PUBLICATION: &str = "summarize_publication";
+const PUBLISH_CHECKLIST: &str = "publish_checklist";
+
+pub fn list_prompts() -> Vec<Prompt> {
+    vec![
+        Prompt::new(
+            SUMMARIZE_PUBLICATION,
+            Some("Summarize a publication: its details, settings and cover image"),
+            Some(vec![publication_name_argument()]),
+        ),
+        Prompt::new(
+            PUBLISH_CHECKLIST,
+            Some("Check whether a publication is ready to be published"),
+            Some(vec![publication_name_argument()]),
+        ),
+    ]
+}
+
+pub fn get_prompt(name: &str, arguments: Option<&JsonObject>) -> Result<GetPromptResult, McpError> {
+    let publication_name = arguments
+        .and_then(|arguments| arguments.get("publication_name"))
+        .and_then(|value| value.as_str())
+        .filter(|value| !value.trim().is_empty())
+        .ok_or_else(|| McpError::invalid_params("publication_name is required", None))?;
+
+    let (description, text) = match name {
+        SUMMARIZE_PUBLICATION => (
+            "Summarize a publication",
+            format!(
+                "Summarize the publication named \"{name}\".\n\n\
+                1. Call get_recent_resources and find the publication whose label matches \"{name}\". \
+                Mention its globalId in your first sentence.\n\
+                2. Call get_resource with that globalId as resource_gid to get its details. \
+                Month values are zero-based, add 1 to get the calendar month.\n\
+                3. Call get_publication_settings with the same globalId.\n\
+                4. If the settings contain coverImage.relUrl, call get_cover_image with it and describe the cover.\n\
+                5. Write a short summary of the publication: name, dates, key settings and cover.",
+                name = publication_name
+            ),
+        ),
+        PUBLISH_CHECKLIST => (
+            "Check a publication before publishing",
+            format!(
+                "Check whether the publication named \"{name}\" is ready to be published.\n\n\
+                1. Call get_recent_resources and find the publication whose label matches \"{name}\". \
+                Mention its globalId in your first sentence.\n\
+                2. Call get_publication_settings with that globalId as resource_gid.\n\
+                3. Check that a cover image is set (coverImage.relUrl) and report whether the \
+                wishlist is enabled (wishlistEnabled).\n\
+                4. Call get_pages with the globalId as publication_gid and flag any empty pages.\n\
+                5. Reply with a checklist of passed and failed items and what to fix before publishing.",
+                name = publication_name
+            ),
+        ),
+        other => {
+            return Err(McpError::invalid_params(
+                format!("Unknown prompt: {}", other),
+                None,
+            ))
+        }
+    };
+
+    Ok(GetPromptResult {
+        description: Some(description.to_string()),
+        messages: vec![PromptMessage::new_text(PromptMessageRole::User, text)],
+    })
+}
+
+fn publication_name_argument() -> PromptArgument {
+    PromptArgument {
+        name: "publication_name".to_string(),
+        title: None,
+        description: Some("The name (label) of the publication".to_string()),
+        required: Some(true),
+    }
+}
@@ -0,0 +1,1709 @@
+use super::*;
+use flate2::{write::GzEncoder, Compression};
+use std::io::Write;
+use wiremock::{
+    matchers::{any, body_json, body_partial_json, header, method, path, query_param},
+    Mock, MockServer, ResponseTemplate,
+};
+
+const CLIENT_ID: &str = "test-client";
+const WP_TOKEN: &str = "test-token";
+
+/// Settings pointing at the mock API. The trailing slash on the API URL
+/// guards against double slashes in the endpoint URLs.
+fn file_config(server: &MockServer) -> FileConfig {
+    FileConfig {
+        api_url: Some(format!("{}/", server.uri())),
+        drive_url: Some(server.uri()),
+        client_id: Some(CLIENT_ID.to_string()),
+        wp_token: Some(WP_TOKEN.to_string()),
+        max_retries: Some(0),
+        ..FileConfig::default()
+    }
+}
+
+fn webpub_with(file: FileConfig) -> WebPublication {
+    let config = ApiConfig::from_sources(file).unwrap();
+    WebPublication::with_config(config, Client::new())
+}
+
+fn webpub(server: &MockServer) -> WebPublication {
+    webpub_with(file_config(server))
+}
+
+fn result_json(result: &CallToolResult) -> serde_json::Value {
+    let text = &result.content[0].as_text().unwrap().text;
+    serde_json::from_str(text).unwrap()
+}
+
+#[tokio::test]
+async fn get_resource_sends_gid_and_cookie() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/workspaceManagerWs/getResource"))
+        .and(query_param("clientId", CLIENT_ID))
+        .and(query_param("resourceGId", "2473843"))
+        .and(header("Cookie", format!("WP_token={}", WP_TOKEN).as_str()))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "globalId": 2473843,
+            "label": "Spring catalogue",
+            "coverImage": { "relUrl": "covers/spring.png" }
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let result = webpub(&server)
+        .get_resource(Parameters(GetResourceRequest {
+            resource_gid: 2473843,
+            fields: Some(vec!["label".to_string()]),
+            client_id: None,
+            locale: None,
+        }))
+        .await
+        .unwrap();
+
+    assert_eq!(
+        result_json(&result),
+        serde_json::json!({ "label": "Spring catalogue" })
+    );
+}
+
+#[tokio::test]
+async fn get_resource_not_found_is_invalid_params() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/workspaceManagerWs/getResource"))
+        .respond_with(ResponseTemplate::new(404))
+        .mount(&server)
+        .await;
+
+    let error = webpub(&server)
+        .get_resource(Parameters(GetResourceRequest {
+            resource_gid: 999,
+            fields: None,
+            client_id: None,
+            locale: None,
+        }))
+        .await
+        .unwrap_err();
+
+    assert_eq!(error.code, rmcp::model::ErrorCode::INVALID_PARAMS);
+    assert!(error.message.contains("get_recent_resources"));
+    assert_eq!(error.data.unwrap()["status"], 404);
+}
+
+#[tokio::test]
+async fn get_resource_rejects_client_id_outside_allowlist() {
+    let server = MockServer::start().await;
+
+    let error = webpub(&server)
+        .get_resource(Parameters(GetResourceRequest {
+            resource_gid: 2473843,
+            fields: None,
+            client_id: Some("other-client".to_string()),
+            locale: None,
+        }))
+        .await
+        .unwrap_err();
+
+    assert_eq!(error.code, rmcp::model::ErrorCode::INVALID_PARAMS);
+    assert!(server.received_requests().await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn get_recent_resources_sends_paging_and_returns_global_ids() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/workspaceManagerWs/getRecentResources"))
+        .and(query_param("clientId", CLIENT_ID))
+        .and(query_param("include", "PUBLICATION"))
+        .and(query_param("itemsPerPage", "5"))
+        .and(query_param("pageNum", "2"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "items": [
+                { "globalId": 11, "label": "First" },
+                { "globalId": 12, "label": "Second" }
+            ]
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let result = webpub(&server)
+        .get_recent_resources(Parameters(RecentResourcesRequest {
+            items_per_page: Some(5),
+            page_num: Some(2),
+            include: None,
+            force_refresh: None,
+            sort_by: None,
+            order: None,
+            client_id: None,
+        }))
+        .await
+        .unwrap();
+
+    let json = result_json(&result);
+    assert_eq!(json["items"][1]["label"], "Second");
+    assert_eq!(
+        json["pagination"],
+        serde_json::json!({
+            "totalCount": null,
+            "pageNum": 2,
+            "itemsPerPage": 5,
+            "hasMore": false
+        })
+    );
+    assert_eq!(
+        result.structured_content.unwrap()["globalIds"],
+        serde_json::json!([11, 12])
+    );
+}
+
+#[tokio::test]
+async fn non_positive_gids_are_rejected_before_any_request() {
+    let server = MockServer::start().await;
+    Mock::given(any())
+        .respond_with(ResponseTemplate::new(200))
+        .expect(0)
+        .mount(&server)
+        .await;
+    let webpub = webpub(&server);
+
+    let errors = [
+        webpub
+            .get_pages(Parameters(GetPagesRequest { publication_gid: 0 }))
+            .await
+            .unwrap_err(),
+        webpub
+            .get_page(Parameters(GetPageRequest { page_gid: -3 }))
+            .await
+            .unwrap_err(),
+        webpub
+            .get_customization(Parameters(GetCustomizationRequest { publication_gid: 0 }))
+            .await
+            .unwrap_err(),
+        webpub
+            .start_enrichment(Parameters(StartEnrichmentRequest {
+                publication_gid: -1,
+                enrichment_type: None,
+            }))
+            .await
+            .unwrap_err(),
+    ];
+
+    for error in errors {
+        assert_eq!(error.code, rmcp::model::ErrorCode::INVALID_PARAMS);
+        assert!(error.message.contains("must be a positive number"));
+    }
+}
+
+#[tokio::test]
+async fn toggle_wishlist_puts_settings_body() {
+    let server = MockServer::start().await;
+    Mock::given(method("PUT"))
+        .and(path("/generationWs/updatePublicationSettings"))
+        .and(query_param("clientId", CLIENT_ID))
+        .and(header("Cookie", format!("WP_token={}", WP_TOKEN).as_str()))
+        .and(body_json(serde_json::json!({
+            "clientId": CLIENT_ID,
+            "globalId": 2473843,
+            "wishlistEnabled": true
+        })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "globalId": 2473843,
+            "wishlistEnabled": true
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let result = webpub(&server)
+        .toggle_wishlist(Parameters(ToggleWishlistRequest {
+            publication_gid: 2473843,
+            wishlist_enabled: true,
+            idempotency_key: None,
+        }))
+        .await
+        .unwrap();
+
+    assert_eq!(result_json(&result)["wishlistEnabled"], true);
+}
+
+#[tokio::test]
+async fn create_gallery_posts_name_and_returns_gallery_gid() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/galleryManagerWs/createGallery"))
+        .and(query_param("clientId", CLIENT_ID))
+        .and(body_json(serde_json::json!({
+            "clientId": CLIENT_ID,
+            "publicationGId": 2473843,
+            "name": "Products"
+        })))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_json(serde_json::json!({ "globalId": 77 })),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let result = webpub(&server)
+        .create_gallery(Parameters(CreateGalleryRequest {
+            publication_gid: 2473843,
+            name: "  Products ".to_string(),
+            idempotency_key: None,
+        }))
+        .await
+        .unwrap();
+
+    assert_eq!(result_json(&result)["galleryGId"], 77);
+}
+
+#[tokio::test]
+async fn add_image_to_gallery_requires_exactly_one_image_reference() {
+    let server = MockServer::start().await;
+
+    let error = webpub(&server)
+        .add_image_to_gallery(Parameters(AddImageToGalleryRequest {
+            gallery_gid: 77,
+            rel_url: Some("images/a.png".to_string()),
+            image_gid: Some(5),
+            caption: None,
+            idempotency_key: None,
+        }))
+        .await
+        .unwrap_err();
+
+    assert_eq!(error.code, rmcp::model::ErrorCode::INVALID_PARAMS);
+    assert!(server.received_requests().await.unwrap().is_empty());
+}
+
+async fn mount_gallery_images(server: &MockServer, gids: &[i64]) {
+    let items: Vec<_> = gids
+        .iter()
+        .map(|gid| serde_json::json!({ "globalId": gid }))
+        .collect();
+    Mock::given(method("GET"))
+        .and(path("/galleryManagerWs/getGalleryImages"))
+        .and(query_param("galleryGId", "77"))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_json(serde_json::json!({ "items": items })),
+        )
+        .mount(server)
+        .await;
+}
+
+#[tokio::test]
+async fn reorder_gallery_images_puts_complete_order() {
+    let server = MockServer::start().await;
+    mount_gallery_images(&server, &[1, 2, 3]).await;
+    Mock::given(method("PUT"))
+        .and(path("/galleryManagerWs/reorderGalleryImages"))
+        .and(body_json(serde_json::json!({
+            "clientId": CLIENT_ID,
+            "galleryGId": 77,
+            "imageGIds": [3, 1, 2]
+        })))
+        .respond_with(ResponseTemplate::new(204))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    webpub(&server)
+        .reorder_gallery_images(Parameters(ReorderGalleryImagesRequest {
+            gallery_gid: 77,
+            image_gids: vec![3, 1, 2],
+        }))
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn reorder_gallery_images_reads_string_image_ids() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/galleryManagerWs/getGalleryImages"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "items": [{ "globalId": "1" }, { "imageGId": "2" }]
+        })))
+        .mount(&server)
+        .await;
+    Mock::given(method("PUT"))
+        .and(path("/galleryManagerWs/reorderGalleryImages"))
+        .respond_with(ResponseTemplate::new(204))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    webpub(&server)
+        .reorder_gallery_images(Parameters(ReorderGalleryImagesRequest {
+            gallery_gid: 77,
+            image_gids: vec![2, 1],
+        }))
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn reorder_gallery_images_rejects_incomplete_order() {
+    let server = MockServer::start().await;
+    mount_gallery_images(&server, &[1, 2, 3]).await;
+    Mock::given(method("PUT"))
+        .respond_with(ResponseTemplate::new(204))
+        .expect(0)
+        .mount(&server)
+        .await;
+
+    let error = webpub(&server)
+        .reorder_gallery_images(Parameters(ReorderGalleryImagesRequest {
+            gallery_gid: 77,
+            image_gids: vec![3, 1, 1, 4],
+        }))
+        .await
+        .unwrap_err();
+
+    assert_eq!(error.code, rmcp::model::ErrorCode::INVALID_PARAMS);
+    assert!(error.message.contains("missing: [2]"));
+    assert!(error.message.contains("not in gallery: [4]"));
+}
+
+#[tokio::test]
+async fn reorder_gallery_images_reads_every_page_of_large_galleries() {
+    let server = MockServer::start().await;
+    let gids: Vec<i64> = (1..=650).collect();
+    for (page_num, page) in gids.chunks(100).enumerate() {
+        let items: Vec<_> = page
+            .iter()
+            .map(|gid| serde_json::json!({ "globalId": gid }))
+            .collect();
+        Mock::given(method("GET"))
+            .and(path("/galleryManagerWs/getGalleryImages"))
+            .and(query_param("pageNum", page_num.to_string()))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_json(serde_json::json!({ "items": items })),
+            )
+            .mount(&server)
+            .await;
+    }
+    Mock::given(method("PUT"))
+        .and(path("/galleryManagerWs/reorderGalleryImages"))
+        .respond_with(ResponseTemplate::new(204))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    webpub(&server)
+        .reorder_gallery_images(Parameters(ReorderGalleryImagesRequest {
+            gallery_gid: 77,
+            image_gids: gids.into_iter().rev().collect(),
+        }))
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn update_publication_title_returns_settings_read_back() {
+    let server = MockServer::start().await;
+    Mock::given(method("PUT"))
+        .and(path("/generationWs/updatePublicationSettings"))
+        .and(body_partial_json(
+            serde_json::json!({ "title": "Autumn catalogue" }),
+        ))
+        .respond_with(ResponseTemplate::new(204))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/generationWs/getPublicationSettings"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "globalId": 2473843,
+            "label": "Autumn catalogue"
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let webpub = webpub(&server);
+    let result = webpub
+        .update_publication_title(Parameters(UpdatePublicationTitleRequest {
+            publication_gid: 2473843,
+            title: " Autumn catalogue ".to_string(),
+        }))
+        .await
+        .unwrap();
+    assert_eq!(result_json(&result)["label"], "Autumn catalogue");
+
+    let error = webpub
+        .update_publication_title(Parameters(UpdatePublicationTitleRequest {
+            publication_gid: 0,
+            title: "Autumn catalogue".to_string(),
+        }))
+        .await
+        .unwrap_err();
+    assert_eq!(error.code, rmcp::model::ErrorCode::INVALID_PARAMS);
+}
+
+#[tokio::test]
+async fn get_publication_url_reports_unpublished_publication() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/generationWs/getPublicationSettings"))
+        .and(query_param("publicationGId", "2473843"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "globalId": 2473843,
+            "published": false,
+            "publicUrl": "https://example.com/p/2473843"
+        })))
+        .mount(&server)
+        .await;
+
+    let result = webpub(&server)
+        .get_publication_url(Parameters(GetPublicationStatusRequest {
+            publication_gid: 2473843,
+        }))
+        .await
+        .unwrap();
+
+    let text = &result.content[0].as_text().unwrap().text;
+    assert!(text.contains("not published"));
+}
+
+#[tokio::test]
+async fn get_requests_accept_json_without_content_type() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/workspaceManagerWs/getResource"))
+        .and(header("Accept", "application/json"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({})))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    webpub(&server)
+        .get_resource(Parameters(GetResourceRequest {
+            resource_gid: 2473843,
+            fields: None,
+            client_id: None,
+            locale: None,
+        }))
+        .await
+        .unwrap();
+
+    let requests = server.received_requests().await.unwrap();
+    assert!(!requests[0].headers.contains_key("content-type"));
+}
+
+#[tokio::test]
+async fn get_cover_image_is_served_from_disk_cache() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/loginWs/refresh"))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_json(serde_json::json!({ "token": "drive" })),
+        )
+        // Cache hits need no drive token
+        .expect(2)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path(format!("/{}/covers/a.png", CLIENT_ID)))
+        .and(query_param("token", "drive"))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_raw(b"\x89PNG\r\n\x1a\n".to_vec(), "image/png"),
+        )
+        .expect(2)
+        .mount(&server)
+        .await;
+
+    let cache_dir = std::env::temp_dir().join(format!("webpub-image-cache-{}", correlation_id()));
+    let webpub = webpub_with(FileConfig {
+        cache_dir: Some(cache_dir.display().to_string()),
+        ..file_config(&server)
+    });
+    let request = |force_refresh| {
+        Parameters(GetCoverImageRequest {
+            rel_url: "covers/a.png".to_string(),
+            force_refresh: Some(force_refresh),
+        })
+    };
+
+    for force_refresh in [false, false, true] {
+        let result = webpub
+            .get_cover_image(request(force_refresh))
+            .await
+            .unwrap();
+        let image = result.content[0].as_image().unwrap();
+        assert_eq!(image.mime_type, "image/png");
+    }
+
+    std::fs::remove_dir_all(cache_dir).ok();
+}
+
+#[tokio::test]
+async fn dry_run_sends_no_drive_or_healthcheck_requests() {
+    let server = MockServer::start().await;
+    Mock::given(any())
+        .respond_with(ResponseTemplate::new(200))
+        .expect(0)
+        .mount(&server)
+        .await;
+    let webpub = webpub_with(FileConfig {
+        dry_run: Some(true),
+        ..file_config(&server)
+    });
+
+    let result = webpub
+        .get_cover_image(Parameters(GetCoverImageRequest {
+            rel_url: "covers/a.png".to_string(),
+            force_refresh: None,
+        }))
+        .await
+        .unwrap();
+    assert_eq!(result_json(&result)["dry_run"], true);
+
+    let result = webpub
+        .get_cover_image_url(Parameters(GetImageRequest {
+            rel_url: "covers/a.png".to_string(),
+        }))
+        .await
+        .unwrap();
+    let url = &result.content[0].as_text().unwrap().text;
+    assert!(url.ends_with("covers/a.png?token=dry-run"));
+
+    let result = webpub.healthcheck().await.unwrap();
+    assert_eq!(result_json(&result)["dry_run"], true);
+}
+
+#[tokio::test]
+async fn upload_image_accepts_image_jpg_in_any_case() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/loginWs/refresh"))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_json(serde_json::json!({ "token": "drive" })),
+        )
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/imageWs/uploadImage"))
+        .and(header("content-type", "image/jpeg"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_json(serde_json::json!({ "relUrl": "images/photo.jpg" })),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let result = webpub(&server)
+        .upload_image(Parameters(UploadImageRequest {
+            data: general_purpose::STANDARD.encode([0xFF, 0xD8, 0xFF, 0xE0]),
+            filename: "photo.jpg".to_string(),
+            mime_type: Some("Image/JPG".to_string()),
+        }))
+        .await
+        .unwrap();
+
+    assert_eq!(result_json(&result)["relUrl"], "images/photo.jpg");
+}
+
+#[tokio::test]
+async fn get_cover_image_url_rejects_paths_off_the_drive() {
+    let server = MockServer::start().await;
+    Mock::given(any())
+        .respond_with(ResponseTemplate::new(200))
+        .expect(0)
+        .mount(&server)
+        .await;
+    let webpub = webpub(&server);
+
+    for rel_url in ["../other-client/x.png", "https://evil.example.com/x.png"] {
+        let error = webpub
+            .get_cover_image_url(Parameters(GetImageRequest {
+                rel_url: rel_url.to_string(),
+            }))
+            .await
+            .unwrap_err();
+        assert_eq!(error.code, rmcp::model::ErrorCode::INVALID_PARAMS);
+    }
+}
+
+#[tokio::test]
+async fn update_enrichment_tags_trims_and_dedupes() {
+    let server = MockServer::start().await;
+    Mock::given(method("PUT"))
+        .and(path("/enrichmentWs/updateTags"))
+        .and(body_json(serde_json::json!({
+            "clientId": CLIENT_ID,
+            "publicationGId": 2473843,
+            "tags": ["spring", "shoes"]
+        })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "tags": ["spring", "shoes"]
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let request = |tags: &[&str]| {
+        Parameters(UpdateEnrichmentTagsRequest {
+            publication_gid: 2473843,
+            tags: tags.iter().map(|tag| tag.to_string()).collect(),
+        })
+    };
+    let webpub = webpub(&server);
+
+    let error = webpub
+        .update_enrichment_tags(request(&["spring", " "]))
+        .await
+        .unwrap_err();
+    assert_eq!(error.code, rmcp::model::ErrorCode::INVALID_PARAMS);
+
+    webpub
+        .update_enrichment_tags(request(&["spring", " shoes", "spring "]))
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn custom_instructions_are_appended_or_replace_built_in_text() {
+    let server = MockServer::start().await;
+    let instructions = |replace_instructions| {
+        webpub_with(FileConfig {
+            instructions: Some("Use British English.".to_string()),
+            replace_instructions: Some(replace_instructions),
+            ..file_config(&server)
+        })
+        .get_info()
+        .instructions
+        .unwrap()
+    };
+
+    let appended = instructions(false);
+    assert!(appended.starts_with(DEFAULT_INSTRUCTIONS));
+    assert!(appended.ends_with("\n\nUse British English."));
+    assert_eq!(instructions(true), "Use British English.");
+}
+
+#[tokio::test]
+async fn get_publication_structure_cuts_tree_at_depth() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/pageManagerWs/getPublicationStructure"))
+        .and(query_param("publicationGId", "2473843"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "items": [{
+                "globalId": 1,
+                "label": "Chapter 1",
+                "children": [{
+                    "globalId": 2,
+                    "label": "Section 1.1",
+                    "children": [{ "globalId": 3 }, { "globalId": 4 }]
+                }]
+            }]
+        })))
+        .mount(&server)
+        .await;
+
+    let result = webpub(&server)
+        .get_publication_structure(Parameters(GetPublicationStructureRequest {
+            publication_gid: 2473843,
+            depth: Some(2),
+        }))
+        .await
+        .unwrap();
+
+    let section = &result_json(&result)["items"][0]["children"][0];
+    assert_eq!(section["label"], "Section 1.1");
+    assert_eq!(section["childCount"], 2);
+    assert!(section.get("children").is_none());
+}
+
+#[tokio::test]
+async fn update_page_content_rejects_empty_content() {
+    let server = MockServer::start().await;
+
+    for content in [
+        serde_json::json!("  "),
+        serde_json::json!([]),
+        serde_json::Value::Null,
+    ] {
+        let error = webpub(&server)
+            .update_page_content(Parameters(UpdatePageContentRequest {
+                page_gid: 31,
+                content,
+            }))
+            .await
+            .unwrap_err();
+        assert_eq!(error.code, rmcp::model::ErrorCode::INVALID_PARAMS);
+    }
+    assert!(server.received_requests().await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn render_page_preview_detects_image_type_from_bytes() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/pageManagerWs/renderPage"))
+        .and(query_param("pageGId", "31"))
+        .and(query_param("format", "jpeg"))
+        .and(query_param("width", "800"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_raw(b"\xFF\xD8\xFF\xE0".to_vec(), "application/octet-stream"),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let result = webpub(&server)
+        .render_page_preview(Parameters(RenderPagePreviewRequest {
+            page_gid: 31,
+            format: Some("jpg".to_string()),
+            width: Some(800),
+        }))
+        .await
+        .unwrap();
+
+    assert_eq!(
+        result.content[0].as_image().unwrap().mime_type,
+        "image/jpeg"
+    );
+}
+
+#[tokio::test]
+async fn mutations_send_given_or_generated_idempotency_key() {
+    let server = MockServer::start().await;
+    Mock::given(method("PUT"))
+        .and(path("/generationWs/updatePublicationSettings"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({})))
+        .mount(&server)
+        .await;
+
+    let webpub = webpub(&server);
+    for idempotency_key in [Some("wishlist-2473843-on".to_string()), None] {
+        webpub
+            .toggle_wishlist(Parameters(ToggleWishlistRequest {
+                publication_gid: 2473843,
+                wishlist_enabled: true,
+                idempotency_key,
+            }))
+            .await
+            .unwrap();
+    }
+
+    let requests = server.received_requests().await.unwrap();
+    let keys: Vec<_> = requests
+        .iter()
+        .map(|request| request.headers["idempotency-key"].to_str().unwrap())
+        .collect();
+    assert_eq!(keys[0], "wishlist-2473843-on");
+    assert_eq!(keys[1].len(), 32);
+}
+
+#[tokio::test]
+async fn update_customization_merges_changes_and_sends_idempotency_key() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/customizationWs/getCustomization"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "primaryColor": "#000000",
+            "fonts": { "body": "Arial", "title": "Georgia" }
+        })))
+        .mount(&server)
+        .await;
+    Mock::given(method("PUT"))
+        .and(path("/customizationWs/updateCustomization"))
+        .and(header("idempotency-key", "theme-2473843"))
+        .and(body_json(serde_json::json!({
+            "clientId": CLIENT_ID,
+            "globalId": 2473843,
+            "primaryColor": "#ff0000",
+            "fonts": { "body": "Inter", "title": "Georgia" }
+        })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({})))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let changes = serde_json::json!({ "primaryColor": "#ff0000", "fonts": { "body": "Inter" } });
+    webpub(&server)
+        .update_customization(Parameters(UpdateCustomizationRequest {
+            publication_gid: 2473843,
+            changes: changes.as_object().unwrap().clone(),
+            idempotency_key: Some("theme-2473843".to_string()),
+        }))
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn toggle_wishlist_bulk_reports_partial_failures() {
+    let server = MockServer::start().await;
+    Mock::given(method("PUT"))
+        .and(path("/generationWs/updatePublicationSettings"))
+        .and(body_partial_json(serde_json::json!({ "globalId": 11 })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({})))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("PUT"))
+        .and(path("/generationWs/updatePublicationSettings"))
+        .and(body_partial_json(serde_json::json!({ "globalId": 12 })))
+        .respond_with(ResponseTemplate::new(500))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let result = webpub(&server)
+        .toggle_wishlist_bulk(Parameters(ToggleWishlistBulkRequest {
+            publication_gids: vec![11, 12, 11],
+            wishlist_enabled: true,
+        }))
+        .await
+        .unwrap();
+
+    let json = result_json(&result);
+    assert_eq!(json["succeeded"], 1);
+    assert_eq!(json["failed"], 1);
+    assert_eq!(json["results"]["11"]["ok"], true);
+    assert_eq!(json["results"]["12"]["ok"], false);
+}
+
+#[tokio::test]
+async fn strict_schema_rejects_unexpected_publication_settings() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/generationWs/getPublicationSettings"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "globalId": "2473843",
+            "label": "Spring catalogue"
+        })))
+        .mount(&server)
+        .await;
+    let request = || {
+        Parameters(GetPublicationSettingsRequest {
+            resource_gid: 2473843,
+            locale: None,
+        })
+    };
+
+    let result = webpub(&server)
+        .get_publication_settings(request())
+        .await
+        .unwrap();
+    assert_eq!(result_json(&result)["globalId"], "2473843");
+
+    let error = webpub_with(FileConfig {
+        strict_schema: Some(true),
+        ..file_config(&server)
+    })
+    .get_publication_settings(request())
+    .await
+    .unwrap_err();
+    assert_eq!(error.code, rmcp::model::ErrorCode::INTERNAL_ERROR);
+    assert!(error.message.contains("publication settings"));
+}
+
+#[tokio::test]
+async fn strict_schema_rejects_only_the_unexpected_recent_resources() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/workspaceManagerWs/getRecentResources"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "totalCount": 3,
+            "items": [
+                { "globalId": "11", "label": "Both dates", "modificationDate": 100, "lastModified": 100 },
+                { "globalId": 12 },
+                { "globalId": 13, "label": "Newer", "modificationDate": 200 }
+            ]
+        })))
+        .mount(&server)
+        .await;
+
+    let result = webpub_with(FileConfig {
+        strict_schema: Some(true),
+        ..file_config(&server)
+    })
+    .get_recent_resources(Parameters(RecentResourcesRequest {
+        items_per_page: None,
+        page_num: None,
+        include: None,
+        force_refresh: None,
+        sort_by: Some("modified".to_string()),
+        order: None,
+        client_id: None,
+    }))
+    .await
+    .unwrap();
+
+    let json = result_json(&result);
+    assert_eq!(json["items"][0]["globalId"], 13);
+    assert_eq!(json["items"][1]["globalId"], "11");
+    assert_eq!(json["rejectedItems"][0]["index"], 1);
+    assert_eq!(json["rejectedItems"][0]["globalId"], 12);
+}
+
+#[tokio::test]
+async fn list_response_without_items_fails_loudly() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/workspaceManagerWs/getRecentResources"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "resources": [{ "globalId": 11, "label": "Spring catalogue" }]
+        })))
+        .mount(&server)
+        .await;
+
+    let error = webpub(&server)
+        .get_recent_resources(Parameters(RecentResourcesRequest {
+            items_per_page: None,
+            page_num: None,
+            include: None,
+            force_refresh: None,
+            sort_by: None,
+            order: None,
+            client_id: None,
+        }))
+        .await
+        .unwrap_err();
+
+    assert_eq!(error.code, rmcp::model::ErrorCode::INTERNAL_ERROR);
+    assert!(error.message.contains("missing field `items`"));
+}
+
+#[tokio::test]
+async fn get_workspace_summary_combines_licence_and_publications() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/licenceWs/getLicence"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "label": "Business",
+            "storageUsed": 1024
+        })))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/workspaceManagerWs/getRecentResources"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "totalCount": 42,
+            "items": [
+                { "globalId": 11, "label": "Older", "modificationDate": 100 },
+                { "globalId": 12, "label": "Newer", "modificationDate": 200 }
+            ]
+        })))
+        .mount(&server)
+        .await;
+
+    let result = webpub(&server).get_workspace_summary().await.unwrap();
+
+    let json = result_json(&result);
+    assert_eq!(json["publicationCount"], 42);
+    assert_eq!(json["plan"], "Business");
+    assert_eq!(json["storageUsed"], 1024);
+    assert_eq!(json["recentActivity"][0]["label"], "Newer");
+    assert_eq!(json["errors"], serde_json::json!([]));
+}
+
+#[tokio::test]
+async fn healthcheck_tells_unparsable_api_from_failing_drive() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/workspaceManagerWs/getRecentResources"))
+        .respond_with(ResponseTemplate::new(200).set_body_string("<html>maintenance</html>"))
+        .mount(&server)
+        .await;
+    Mock::given(method("HEAD"))
+        .and(path("/"))
+        .respond_with(ResponseTemplate::new(503))
+        .mount(&server)
+        .await;
+
+    let result = webpub(&server).healthcheck().await.unwrap();
+
+    let json = result_json(&result);
+    assert_eq!(json["api_reachable"], true);
+    assert_eq!(json["auth_valid"], true);
+    assert!(json["api_error"].is_string());
+    assert_eq!(json["drive_reachable"], false);
+    assert!(json["drive_error"].as_str().unwrap().contains("503"));
+}
+
+#[tokio::test]
+async fn get_workspace_summary_survives_unsortable_publications() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/licenceWs/getLicence"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "label": "Business",
+            "storageUsed": 1024
+        })))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/workspaceManagerWs/getRecentResources"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "totalCount": 2,
+            "items": [
+                { "globalId": 11, "label": "First", "modificationDate": 100 },
+                { "globalId": 12, "label": ["not", "a", "label"] }
+            ]
+        })))
+        .mount(&server)
+        .await;
+
+    let result = webpub(&server).get_workspace_summary().await.unwrap();
+
+    let json = result_json(&result);
+    assert_eq!(json["plan"], "Business");
+    assert_eq!(json["publicationCount"], 2);
+    assert_eq!(json["recentActivity"][0]["label"], "First");
+    assert!(json["errors"][0]
+        .as_str()
+        .unwrap()
+        .starts_with("recent activity order"));
+}
+
+#[test]
+fn public_config_leaves_out_secrets() {
+    let config = webpub_with(FileConfig {
+        api_url: Some("https://api.example.com".to_string()),
+        drive_url: Some("https://drive.example.com".to_string()),
+        client_id: Some(CLIENT_ID.to_string()),
+        wp_token: Some(WP_TOKEN.to_string()),
+        password: Some("hunter2".to_string()),
+        ..FileConfig::default()
+    })
+    .public_config();
+
+    assert_eq!(config["client_id"], CLIENT_ID);
+    assert_eq!(config["drive_url"], "https://drive.example.com");
+    let text = config.to_string();
+    assert!(!text.contains(WP_TOKEN));
+    assert!(!text.contains("hunter2"));
+}
+
+#[tokio::test]
+async fn get_cover_image_by_gid_reports_missing_cover() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/generationWs/getPublicationSettings"))
+        .and(query_param("publicationGId", "2473843"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "globalId": 2473843,
+            "coverImage": null
+        })))
+        .mount(&server)
+        .await;
+
+    let result = webpub(&server)
+        .get_cover_image_by_gid(Parameters(GetCoverImageByGidRequest {
+            publication_gid: 2473843,
+            force_refresh: None,
+        }))
+        .await
+        .unwrap();
+
+    let text = &result.content[0].as_text().unwrap().text;
+    assert!(text.contains("has no cover image"));
+}
+
+#[tokio::test]
+async fn get_recent_resources_uses_configured_default_page_size() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/workspaceManagerWs/getRecentResources"))
+        .and(query_param("itemsPerPage", "50"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({ "items": [] })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    webpub_with(FileConfig {
+        recent_items_default: Some(50),
+        ..file_config(&server)
+    })
+    .get_recent_resources(Parameters(RecentResourcesRequest {
+        items_per_page: None,
+        page_num: None,
+        include: None,
+        force_refresh: None,
+        sort_by: None,
+        order: None,
+        client_id: None,
+    }))
+    .await
+    .unwrap();
+}
+
+#[tokio::test]
+async fn export_publications_csv_escapes_labels_and_looks_up_wishlist() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/workspaceManagerWs/getRecentResources"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "items": [
+                {
+                    "globalId": 11,
+                    "label": "Shoes, \"Spring\"",
+                    "modificationDate": { "year": 2024, "month": 5, "dayOfMonth": 3 },
+                    "wishlistEnabled": true
+                },
+                { "globalId": "12", "label": "Plain" }
+            ]
+        })))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/generationWs/getPublicationSettings"))
+        .and(query_param("publicationGId", "12"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_json(serde_json::json!({ "wishlistEnabled": false })),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let result = webpub(&server)
+        .export_publications_csv(Parameters(ListAllPublicationsRequest { limit: None }))
+        .await
+        .unwrap();
+
+    assert_eq!(
+        result.content[0].as_text().unwrap().text,
+        "globalId,label,modified,wishlistEnabled\n\
+         11,\"Shoes, \"\"Spring\"\"\",2024-06-03,true\n\
+         12,Plain,,false\n"
+    );
+}
+
+#[tokio::test]
+async fn gzipped_responses_are_decompressed() {
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+    encoder
+        .write_all(br#"{"globalId": 2473843, "label": "Spring catalogue"}"#)
+        .unwrap();
+    let body = encoder.finish().unwrap();
+
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/workspaceManagerWs/getResource"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .insert_header("Content-Encoding", "gzip")
+                .set_body_raw(body, "application/json"),
+        )
+        .mount(&server)
+        .await;
+
+    let result = webpub(&server)
+        .get_resource(Parameters(GetResourceRequest {
+            resource_gid: 2473843,
+            fields: None,
+            client_id: None,
+            locale: None,
+        }))
+        .await
+        .unwrap();
+
+    assert_eq!(result_json(&result)["label"], "Spring catalogue");
+    let requests = server.received_requests().await.unwrap();
+    let accept_encoding = requests[0].headers["accept-encoding"].to_str().unwrap();
+    assert!(accept_encoding.contains("gzip"));
+    assert!(accept_encoding.contains("br"));
+}
+
+#[tokio::test]
+async fn oversized_json_response_is_rejected() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/workspaceManagerWs/getResource"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "globalId": 2473843,
+            "label": "x".repeat(2048)
+        })))
+        .mount(&server)
+        .await;
+
+    let error = webpub_with(FileConfig {
+        max_json_bytes: Some(1024),
+        ..file_config(&server)
+    })
+    .get_resource(Parameters(GetResourceRequest {
+        resource_gid: 2473843,
+        fields: None,
+        client_id: None,
+        locale: None,
+    }))
+    .await
+    .unwrap_err();
+
+    assert!(error.message.contains("MAX_JSON_BYTES"));
+}
+
+#[tokio::test]
+async fn start_enrichment_outlasts_the_global_timeout() {
+    let server = MockServer::start().await;
+    let slow = ResponseTemplate::new(200)
+        .set_body_json(serde_json::json!({ "jobId": "job-1" }))
+        .set_delay(Duration::from_millis(300));
+    Mock::given(method("POST"))
+        .and(path("/enrichmentWs/startEnrichment"))
+        .respond_with(slow.clone())
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/enrichmentWs/getEnrichmentStatus"))
+        .respond_with(slow)
+        .mount(&server)
+        .await;
+
+    let config = ApiConfig::from_sources(file_config(&server)).unwrap();
+    let client = Client::builder()
+        .timeout(Duration::from_millis(100))
+        .build()
+        .unwrap();
+    let webpub = WebPublication::with_config(config, client);
+
+    let result = webpub
+        .start_enrichment(Parameters(StartEnrichmentRequest {
+            publication_gid: 2473843,
+            enrichment_type: None,
+        }))
+        .await
+        .unwrap();
+    assert_eq!(result_json(&result)["jobId"], "job-1");
+
+    let error = webpub
+        .get_enrichment_status(Parameters(GetEnrichmentStatusRequest {
+            job_id: "job-1".to_string(),
+        }))
+        .await
+        .unwrap_err();
+    assert!(error.message.contains("timed out"), "{}", error.message);
+}
+
+#[tokio::test]
+async fn get_resource_version_sends_version_id() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/workspaceManagerWs/getResourceVersion"))
+        .and(query_param("resourceGId", "2473843"))
+        .and(query_param("versionId", "v7"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "versionId": "v7",
+            "label": "Spring catalogue (draft)"
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let webpub = webpub(&server);
+    let result = webpub
+        .get_resource_version(Parameters(GetResourceVersionRequest {
+            resource_gid: 2473843,
+            version_id: " v7 ".to_string(),
+        }))
+        .await
+        .unwrap();
+    assert_eq!(result_json(&result)["label"], "Spring catalogue (draft)");
+
+    let error = webpub
+        .get_resource_version(Parameters(GetResourceVersionRequest {
+            resource_gid: 2473843,
+            version_id: "  ".to_string(),
+        }))
+        .await
+        .unwrap_err();
+    assert_eq!(error.code, rmcp::model::ErrorCode::INVALID_PARAMS);
+}
+
+#[tokio::test]
+async fn restore_resource_version_checks_version_list_first() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/workspaceManagerWs/getResourceVersions"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "versions": [
+                { "versionId": "v6", "author": "ana" },
+                { "versionId": 7, "author": "ben" }
+            ]
+        })))
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/workspaceManagerWs/restoreResourceVersion"))
+        .and(body_json(serde_json::json!({
+            "clientId": CLIENT_ID,
+            "resourceGId": 2473843,
+            "versionId": "7"
+        })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({})))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/workspaceManagerWs/getResource"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "globalId": 2473843,
+            "label": "Spring catalogue"
+        })))
+        .mount(&server)
+        .await;
+
+    let webpub = webpub(&server);
+    let error = webpub
+        .restore_resource_version(Parameters(RestoreResourceVersionRequest {
+            resource_gid: 2473843,
+            version_id: "v8".to_string(),
+            idempotency_key: None,
+        }))
+        .await
+        .unwrap_err();
+    assert_eq!(error.code, rmcp::model::ErrorCode::INVALID_PARAMS);
+
+    let result = webpub
+        .restore_resource_version(Parameters(RestoreResourceVersionRequest {
+            resource_gid: 2473843,
+            version_id: "7".to_string(),
+            idempotency_key: None,
+        }))
+        .await
+        .unwrap();
+    assert_eq!(result_json(&result)["label"], "Spring catalogue");
+}
+
+#[tokio::test]
+async fn max_concurrent_requests_queues_extra_calls() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/workspaceManagerWs/getResource"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_json(serde_json::json!({ "globalId": 2473843 }))
+                .set_delay(Duration::from_millis(200)),
+        )
+        .expect(2)
+        .mount(&server)
+        .await;
+
+    let webpub = webpub_with(FileConfig {
+        max_concurrent_requests: Some(1),
+        ..file_config(&server)
+    });
+    let get = || {
+        webpub.get_resource(Parameters(GetResourceRequest {
+            resource_gid: 2473843,
+            fields: None,
+            client_id: None,
+            locale: None,
+        }))
+    };
+
+    let started = Instant::now();
+    let (first, second) = tokio::join!(get(), get());
+    first.unwrap();
+    second.unwrap();
+    assert!(started.elapsed() >= Duration::from_millis(400));
+}
+
+#[tokio::test]
+async fn request_slot_is_released_while_backing_off() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/workspaceManagerWs/getResource"))
+        .respond_with(ResponseTemplate::new(503))
+        .up_to_n_times(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/workspaceManagerWs/getResource"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({})))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/pageManagerWs/getPages"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({ "items": [] })))
+        .mount(&server)
+        .await;
+
+    let webpub = webpub_with(FileConfig {
+        max_concurrent_requests: Some(1),
+        max_retries: Some(1),
+        retry_base_delay_ms: Some(1000),
+        ..file_config(&server)
+    });
+
+    let retried = webpub.get_resource(Parameters(GetResourceRequest {
+        resource_gid: 2473843,
+        fields: None,
+        client_id: None,
+        locale: None,
+    }));
+    let parallel = async {
+        // Starts once the first call is backing off after its 503
+        tokio::time::sleep(Duration::from_millis(200)).await;
+        let started = Instant::now();
+        webpub
+            .get_pages(Parameters(GetPagesRequest {
+                publication_gid: 2473843,
+            }))
+            .await
+            .unwrap();
+        started.elapsed()
+    };
+
+    let (retried, parallel_elapsed) = tokio::join!(retried, parallel);
+    retried.unwrap();
+    assert!(parallel_elapsed < Duration::from_millis(500));
+}
+
+#[tokio::test]
+async fn find_publications_by_date_filters_every_date_format() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/workspaceManagerWs/getRecentResources"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "items": [
+                {
+                    "globalId": 11,
+                    "label": "Parts",
+                    "modificationDate": { "year": 2025, "month": 9, "dayOfMonth": 8 }
+                },
+                // 2025-10-09T08:53:20Z
+                { "globalId": 12, "label": "Millis", "modificationDate": 1760000000000_i64 },
+                { "globalId": 13, "label": "Too late", "modificationDate": "2025-10-12T09:00:00Z" },
+                { "globalId": 14, "label": "Undated" }
+            ]
+        })))
+        .mount(&server)
+        .await;
+
+    let webpub = webpub(&server);
+    let result = webpub
+        .find_publications_by_date(Parameters(FindPublicationsByDateRequest {
+            from: Some("2025-10-05".to_string()),
+            to: Some("2025-10-11".to_string()),
+            date_field: None,
+        }))
+        .await
+        .unwrap();
+
+    let listing = result_json(&result);
+    assert_eq!(listing["count"], 2);
+    assert_eq!(listing["items"][0]["globalId"], 12);
+    assert_eq!(listing["items"][1]["modified"], "2025-10-08");
+
+    let error = webpub
+        .find_publications_by_date(Parameters(FindPublicationsByDateRequest {
+            from: Some("last week".to_string()),
+            to: None,
+            date_field: None,
+        }))
+        .await
+        .unwrap_err();
+    assert_eq!(error.code, rmcp::model::ErrorCode::INVALID_PARAMS);
+}
+
+#[tokio::test]
+async fn get_publication_settings_forwards_locale() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/generationWs/getPublicationSettings"))
+        .and(query_param("locale", "pt-BR"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "globalId": 2473843,
+            "label": "Catálogo de primavera"
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let webpub = webpub(&server);
+    let result = webpub
+        .get_publication_settings(Parameters(GetPublicationSettingsRequest {
+            resource_gid: 2473843,
+            locale: Some("pt-BR".to_string()),
+        }))
+        .await
+        .unwrap();
+    assert_eq!(result_json(&result)["label"], "Catálogo de primavera");
+
+    let error = webpub
+        .get_publication_settings(Parameters(GetPublicationSettingsRequest {
+            resource_gid: 2473843,
+            locale: Some("en&admin=1".to_string()),
+        }))
+        .await
+        .unwrap_err();
+    assert_eq!(error.code, rmcp::model::ErrorCode::INVALID_PARAMS);
+}
+
+#[tokio::test]
+async fn validate_publication_reports_missing_cover_and_empty_pages() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/generationWs/getPublicationSettings"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "globalId": 2473843,
+            "label": "Spring catalogue",
+            "coverImage": null
+        })))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/pageManagerWs/getPages"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "items": [
+                { "globalId": 31, "label": "Cover", "content": "<h1>Spring</h1>" },
+                { "globalId": 32, "label": "Blank", "content": " " }
+            ]
+        })))
+        .mount(&server)
+        .await;
+
+    let result = webpub(&server)
+        .validate_publication(Parameters(ValidatePublicationRequest {
+            publication_gid: 2473843,
+        }))
+        .await
+        .unwrap();
+
+    let report = result_json(&result);
+    assert_eq!(report["ready"], false);
+    let codes = report["issues"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|issue| issue["code"].as_str().unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(codes, ["missing_cover", "empty_page"]);
+    assert_eq!(report["issues"][1]["pageGId"], 32);
+}
+
+#[tokio::test]
+async fn publish_publication_forwards_schedule() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/generationWs/publish"))
+        .and(body_json(serde_json::json!({
+            "clientId": CLIENT_ID,
+            "publicationGId": 2473843,
+            "scheduledAt": "2025-10-20T09:00:00Z"
+        })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({})))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/generationWs/getPublicationSettings"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_json(serde_json::json!({ "globalId": 2473843, "published": false })),
+        )
+        .mount(&server)
+        .await;
+
+    let result = webpub(&server)
+        .publish_publication(Parameters(PublishPublicationRequest {
+            publication_gid: 2473843,
+            schedule_at: Some("2025-10-20T09:00:00Z".to_string()),
+        }))
+        .await
+        .unwrap();
+
+    let status = result_json(&result);
+    assert_eq!(status["status"], "scheduled");
+    assert_eq!(status["scheduledAt"], "2025-10-20T09:00:00Z");
+    assert_eq!(status["url"], serde_json::Value::Null);
+}
+
+#[tokio::test]
+async fn unpublish_publication_skips_drafts() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/generationWs/getPublicationSettings"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_json(serde_json::json!({ "globalId": 2473843, "published": false })),
+        )
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/generationWs/unpublish"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({})))
+        .expect(0)
+        .mount(&server)
+        .await;
+
+    let result = webpub(&server)
+        .unpublish_publication(Parameters(UnpublishPublicationRequest {
+            publication_gid: 2473843,
+        }))
+        .await
+        .unwrap();
+
+    let status = result_json(&result);
+    assert_eq!(status["status"], "draft");
+    assert_eq!(status["changed"], false);
+}
+
+#[test]
+fn large_text_contents_are_split_at_line_breaks() {
+    let text = "{\n  \"label\": \"Spring\",\n  \"globalId\": 2473843\n}";
+    let result = CallToolResult::success(vec![Content::text(text)]);
+
+    let split = split_large_contents(result, 20);
+
+    let texts = split
+        .content
+        .iter()
+        .map(|content| content.as_text().unwrap().text.as_str())
+        .collect::<Vec<_>>();
+    assert!(texts[0].contains("split into the 4 items"), "{}", texts[0]);
+    assert_eq!(texts[1..].concat(), text);
+    assert!(texts[1..].iter().all(|part| part.chars().count() <= 20));
+    assert_eq!(texts[1], "{\n");
+
+    let untouched = split_large_contents(CallToolResult::success(vec![Content::text(text)]), 0);
+    assert_eq!(untouched.content.len(), 1);
+}
+
+#[test]
+fn publication_resources_accept_string_gids() {
+    let resource = publication_resource(serde_json::json!({
+        "globalId": "2473843",
+        "label": "Spring catalogue"
+    }))
+    .unwrap();
+    assert_eq!(resource.uri, "webpub://publication/2473843");
+    assert_eq!(resource.name, "Spring catalogue");
+
+    assert!(publication_resource(serde_json::json!({ "label": "No gid" })).is_none());
+}
+
+#[test]
+fn gid_input_schemas_accept_numeric_strings() {
+    let schema = serde_json::to_value(schemars::schema_for!(GetResourceRequest)).unwrap();
+    let resource_gid = &schema["properties"]["resource_gid"];
+    assert_eq!(
+        resource_gid["type"],
+        serde_json::json!(["integer", "string"])
+    );
+    assert!(resource_gid["pattern"].is_string());
+
+    let schema = serde_json::to_value(schemars::schema_for!(ToggleWishlistBulkRequest)).unwrap();
+    let items = &schema["properties"]["publication_gids"]["items"];
+    assert_eq!(items["type"], serde_json::json!(["integer", "string"]));
+
+    let schema = serde_json::to_value(schemars::schema_for!(GetGalleriesRequest)).unwrap();
+    assert!(schema["properties"]["publication_gid"]["type"]
+        .as_array()
+        .unwrap()
+        .contains(&serde_json::json!("null")));
+    assert!(!schema["required"]
+        .as_array()
+        .is_some_and(|required| required.contains(&serde_json::json!("publication_gid"))));
+}